@@ -3,17 +3,19 @@ use async_std::{
 	sync::{Arc, Mutex},
 	task,
 };
+use std::collections::VecDeque;
 use std::io;
 use tui::backend::{Backend, CrosstermBackend};
 use tui::layout::{Constraint, Direction, Layout};
 use tui::style::{Color, Modifier, Style};
-use tui::widgets::{Block, Borders, Gauge, List, ListItem, Row, Table};
+use tui::widgets::canvas::{Canvas, Points};
+use tui::widgets::{Block, Borders, Gauge, List, ListItem, Row, Sparkline, Table};
 use tui::Terminal;
 
 use crossbeam_channel::Receiver;
 use f1_telemetry_client::{
 	f1_2020::car::CarStatusData, f1_2020::event::Event, f1_2020::nationality::Nationality,
-	f1_2020::packet::Packet2020,
+	f1_2020::packet::Packet2020, f1_2020::session::SessionType,
 };
 use std::time::Duration;
 
@@ -38,6 +40,9 @@ pub struct PositionTable {
 	s3: String,
 	tyre: String,
 	current_lap_num: u8,
+	lap_distance: f32,
+	total_distance: f32,
+	gap_ahead: String,
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +66,45 @@ pub struct PlayerTelemetry {
 	rev_lights_percent: u8,
 }
 
+const SAMPLE_BUFFER_CAPACITY: usize = 256;
+const RADAR_RADIUS_METERS: f32 = 30.0;
+const CAR_LENGTH_METERS: f32 = 5.0;
+const TRACK_PATH_MIN_STEP_METERS: f32 = 1.0;
+const DELTA_SEGMENTS: usize = 50;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RadarBlip {
+	x: f32,
+	y: f32,
+	alongside: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SampleBuffer {
+	samples: VecDeque<u64>,
+	capacity: usize,
+}
+
+impl SampleBuffer {
+	fn new(capacity: usize) -> Self {
+		SampleBuffer {
+			samples: VecDeque::with_capacity(capacity),
+			capacity,
+		}
+	}
+
+	fn push(&mut self, value: u64) {
+		if self.samples.len() == self.capacity {
+			self.samples.pop_front();
+		}
+		self.samples.push_back(value);
+	}
+
+	fn to_vec(&self) -> Vec<u64> {
+		self.samples.iter().copied().collect()
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct AppData {
 	// player
@@ -72,6 +116,71 @@ pub struct AppData {
 	participants: Vec<DriverDetails>,
 	car_status: Vec<CarStatus>,
 	speed_trap: Option<f32>,
+	throttle_samples: SampleBuffer,
+	brake_samples: SampleBuffer,
+	radar: Vec<RadarBlip>,
+	player_current_lap: u8,
+	track_path: Vec<(f32, f32)>,
+	minimap_cars: Vec<(f32, f32, bool)>,
+	session_uid: u64,
+	is_time_trial: bool,
+	track_length_estimate: f32,
+	delta_reference: Vec<f32>,
+	current_delta: Option<f32>,
+}
+
+impl AppData {
+	fn reset_session(&mut self) {
+		self.player_details = None;
+		self.player_car_status = None;
+		self.player_telemetry = None;
+		self.positions_table.clear();
+		self.participants.clear();
+		self.car_status.clear();
+		self.speed_trap = None;
+		self.throttle_samples = SampleBuffer::new(SAMPLE_BUFFER_CAPACITY);
+		self.brake_samples = SampleBuffer::new(SAMPLE_BUFFER_CAPACITY);
+		self.radar.clear();
+		self.player_current_lap = 0;
+		self.track_path.clear();
+		self.minimap_cars.clear();
+		self.track_length_estimate = 0.0;
+		self.delta_reference = vec![f32::INFINITY; DELTA_SEGMENTS];
+		self.current_delta = None;
+	}
+
+	fn update_delta(&mut self, lap_distance: f32, current_lap_time: f32) {
+		if lap_distance > self.track_length_estimate {
+			self.track_length_estimate = lap_distance;
+		}
+
+		if self.track_length_estimate <= 0.0 {
+			return;
+		}
+
+		let segment_span = self.track_length_estimate / DELTA_SEGMENTS as f32;
+		let segment_f = lap_distance / segment_span;
+		let segment = (segment_f as usize).min(DELTA_SEGMENTS - 1);
+
+		if current_lap_time < self.delta_reference[segment] {
+			self.delta_reference[segment] = current_lap_time;
+		}
+
+		let left = segment;
+		let right = (left + 1).min(DELTA_SEGMENTS - 1);
+		let frac = segment_f.fract();
+		let left_ref = self.delta_reference[left];
+		let right_ref = self.delta_reference[right];
+
+		self.current_delta = if left_ref.is_finite() && right_ref.is_finite() {
+			let reference_time = left_ref * (1.0 - frac) + right_ref * frac;
+			Some(current_lap_time - reference_time)
+		} else if left_ref.is_finite() {
+			Some(current_lap_time - left_ref)
+		} else {
+			None
+		};
+	}
 }
 
 #[derive(Debug, Clone)]
@@ -91,6 +200,17 @@ impl App {
 				participants: Vec::with_capacity(22),
 				car_status: Vec::with_capacity(22),
 				speed_trap: None,
+				throttle_samples: SampleBuffer::new(SAMPLE_BUFFER_CAPACITY),
+				brake_samples: SampleBuffer::new(SAMPLE_BUFFER_CAPACITY),
+				radar: Vec::new(),
+				player_current_lap: 0,
+				track_path: Vec::new(),
+				minimap_cars: Vec::new(),
+				session_uid: 0,
+				is_time_trial: false,
+				track_length_estimate: 0.0,
+				delta_reference: vec![f32::INFINITY; DELTA_SEGMENTS],
+				current_delta: None,
 			})),
 		}
 	}
@@ -106,7 +226,75 @@ impl App {
 			for msg in receiver {
 				let mut data = app_data.lock().await;
 				match msg {
-					Packet2020::Motion(_motion) => {}
+					Packet2020::Session(session) => {
+						let session_uid = session.header.session_uid;
+
+						if data.session_uid != 0 && data.session_uid != session_uid {
+							data.reset_session();
+						}
+
+						data.session_uid = session_uid;
+						data.is_time_trial = matches!(session.session_type, SessionType::TimeTrial);
+					}
+					Packet2020::Motion(motion) => {
+						let player_index = motion.header.player_car_index as usize;
+
+						if let Some(player) = motion.motion_data.get(player_index) {
+							let yaw = player.yaw;
+							let player_x = player.world_position_x;
+							let player_z = player.world_position_z;
+
+							if data.player_current_lap <= 1 {
+								let moved_enough = data
+								  .track_path
+								  .last()
+								  .map(|(last_x, last_z)| {
+									  let dx = player_x - last_x;
+									  let dz = player_z - last_z;
+									  (dx * dx + dz * dz).sqrt() >= TRACK_PATH_MIN_STEP_METERS
+								  })
+								  .unwrap_or(true);
+
+								if moved_enough {
+									data.track_path.push((player_x, player_z));
+								}
+							}
+
+							data.minimap_cars = motion
+							  .motion_data
+							  .iter()
+							  .enumerate()
+							  .map(|(i, car)| {
+								  (car.world_position_x, car.world_position_z, i == player_index)
+							  })
+							  .collect();
+
+							data.radar = motion
+							  .motion_data
+							  .iter()
+							  .enumerate()
+							  .filter(|(i, _)| *i != player_index)
+							  .filter_map(|(_, car)| {
+								  let dx = car.world_position_x - player_x;
+								  let dz = car.world_position_z - player_z;
+								  let distance = (dx * dx + dz * dz).sqrt();
+
+								  if distance > RADAR_RADIUS_METERS {
+									  return None;
+								  }
+
+								  let local_x = dx * yaw.cos() - dz * yaw.sin();
+								  let local_y = dx * yaw.sin() + dz * yaw.cos();
+
+								  Some(RadarBlip {
+									  x: local_x,
+									  y: local_y,
+									  alongside: local_y.abs() <= CAR_LENGTH_METERS,
+								  })
+							  })
+							  .collect();
+						}
+					}
 					Packet2020::CarStatus(car_status) => {
 						let player_index = car_status.header.player_car_index as usize;
 
@@ -134,6 +322,11 @@ impl App {
 						if let Some(player_telemetry) =
 						car_telemetry.car_telemetry_data.get(player_index)
 						{
+							data.throttle_samples
+							  .push((player_telemetry.throttle * 100.0).round() as u64);
+							data.brake_samples
+							  .push((player_telemetry.brake * 100.0).round() as u64);
+
 							data.player_telemetry = Some(PlayerTelemetry {
 								speed: player_telemetry.speed,
 								throttle: player_telemetry.throttle,
@@ -207,11 +400,31 @@ impl App {
 									  s3: to_lap_time(lap.best_lap_sector_1_time),
 									  tyre: car.tyre.clone(),
 									  current_lap_num: lap.current_lap_num,
+									  lap_distance: lap.lap_distance,
+									  total_distance: lap.total_distance,
+									  gap_ahead: String::new(),
 								  }
 							  })
 							  .collect();
 
 							data.positions_table.sort_by_key(|p| p.position);
+
+							let mut ahead_total_distance: Option<f32> = None;
+							for car in data.positions_table.iter_mut() {
+								car.gap_ahead = match ahead_total_distance {
+									Some(distance) => format!("{:.1}m", (distance - car.total_distance).max(0.0)),
+									None => "Leader".to_string(),
+								};
+								ahead_total_distance = Some(car.total_distance);
+							}
+
+							if let Some(player_lap) = lap.get(player_index) {
+								data.player_current_lap = player_lap.current_lap_num;
+								data.update_delta(
+									player_lap.lap_distance.max(0.0),
+									player_lap.current_lap_time.as_secs_f32(),
+								);
+							}
 						}
 					}
 					Packet2020::Event(event) => {
@@ -231,6 +444,8 @@ impl App {
 				terminal.autoresize().unwrap();
 				terminal
 				  .draw(|f| {
+					  let is_time_trial = data.is_time_trial;
+
 					  let chunks = Layout::default()
 						.direction(Direction::Horizontal)
 						.margin(1)
@@ -246,11 +461,13 @@ impl App {
 						.margin(1)
 						.constraints(
 							[
-								Constraint::Percentage(35), // car status
-								Constraint::Percentage(10), // rev light
-								Constraint::Percentage(10), // rev light
-								Constraint::Percentage(10), // rev light
-								Constraint::Percentage(35), // car telemetry
+								Constraint::Percentage(30), // car status
+								Constraint::Percentage(8),  // rev light
+								Constraint::Percentage(8),  // brake gauge
+								Constraint::Percentage(8),  // throttle gauge
+								Constraint::Percentage(8),  // throttle trace
+								Constraint::Percentage(8),  // brake trace
+								Constraint::Percentage(30), // car telemetry
 							]
 							  .as_ref(),
 						)
@@ -350,9 +567,16 @@ impl App {
 								.style(Style::default().fg(Color::White)),
 						  ];
 
-						  let items = List::new(items)
-							.block(Block::default().borders(Borders::ALL).title("Status"));
-						  f.render_widget(items, status_layout[0]);
+						  if is_time_trial {
+							  let status_placeholder = Block::default()
+								.borders(Borders::ALL)
+								.title("Status (unavailable for this session)");
+							  f.render_widget(status_placeholder, status_layout[0]);
+						  } else {
+							  let items = List::new(items)
+								.block(Block::default().borders(Borders::ALL).title("Status"));
+							  f.render_widget(items, status_layout[0]);
+						  }
 					  };
 
 					  let car_telemetry_layout = Layout::default()
@@ -366,7 +590,21 @@ impl App {
 							]
 							  .as_ref(),
 						)
-						.split(left_layout[4]);
+						.split(left_layout[6]);
+
+					  let throttle_trace = Sparkline::default()
+						.block(Block::default().title("Throttle Trace").borders(Borders::ALL))
+						.style(Style::default().fg(Color::Green))
+						.data(&data.throttle_samples.to_vec())
+						.max(100);
+					  f.render_widget(throttle_trace, left_layout[4]);
+
+					  let brake_trace = Sparkline::default()
+						.block(Block::default().title("Brake Trace").borders(Borders::ALL))
+						.style(Style::default().fg(Color::Red))
+						.data(&data.brake_samples.to_vec())
+						.max(100);
+					  f.render_widget(brake_trace, left_layout[5]);
 
 					  if let Some(car_data) = data.player_telemetry.clone() {
 						  let rev_light_color =
@@ -412,6 +650,8 @@ impl App {
 								.style(Style::default().fg(Color::White)),
 							  ListItem::new(format!("Throttle: {}", car_data.throttle))
 								.style(Style::default().fg(Color::White)),
+							  ListItem::new(delta_text(data.current_delta))
+								.style(delta_style(data.current_delta)),
 						  ];
 
 						  let car_info_list = List::new(car_info_list)
@@ -425,7 +665,12 @@ impl App {
 
 					  let right_layout = Layout::default()
 						.constraints(
-							[Constraint::Percentage(100)].as_ref(),
+							[
+								Constraint::Percentage(50),
+								Constraint::Percentage(25),
+								Constraint::Percentage(25),
+							]
+							  .as_ref(),
 						)
 						.split(chunks[1]);
 
@@ -448,6 +693,7 @@ impl App {
 								  p.position.to_string(),
 								  last_name,
 								  p.current_lap_num.to_string(),
+								  p.gap_ahead.clone(),
 								  p.last_lap.clone(),
 								  p.best_lap.clone(),
 								  p.tyre.clone(),
@@ -458,7 +704,7 @@ impl App {
 					  });
 
 					  let live_position = Table::new(
-						  ["P", "Driver", "Lap", "Last Lap", "Best Lap", "Tyre"].iter(),
+						  ["P", "Driver", "Lap", "Gap", "Last Lap", "Best Lap", "Tyre"].iter(),
 						  positions.clone().into_iter(),
 					  )
 						.block(
@@ -470,6 +716,7 @@ impl App {
 							Constraint::Length(2),
 							Constraint::Length(10),
 							Constraint::Length(3),
+							Constraint::Length(7),
 							Constraint::Length(8),
 							Constraint::Length(8),
 							Constraint::Length(5),
@@ -477,7 +724,98 @@ impl App {
 						.style(Style::default().fg(Color::White))
 						.column_spacing(5);
 
-					  f.render_widget(live_position, right_layout[0]);
+					  if !is_time_trial && data.positions_table.len() > 1 {
+						  f.render_widget(live_position, right_layout[0]);
+					  } else {
+						  let live_position_placeholder = Block::default()
+							.borders(Borders::ALL)
+							.title("Live Position (unavailable for this session)");
+						  f.render_widget(live_position_placeholder, right_layout[0]);
+					  }
+
+					  let alongside: Vec<(f64, f64)> = data
+						.radar
+						.iter()
+						.filter(|b| b.alongside)
+						.map(|b| (b.x as f64, b.y as f64))
+						.collect();
+					  let nearby: Vec<(f64, f64)> = data
+						.radar
+						.iter()
+						.filter(|b| !b.alongside)
+						.map(|b| (b.x as f64, b.y as f64))
+						.collect();
+
+					  let radar = Canvas::default()
+						.block(Block::default().title("Radar").borders(Borders::ALL))
+						.x_bounds([-RADAR_RADIUS_METERS as f64, RADAR_RADIUS_METERS as f64])
+						.y_bounds([-RADAR_RADIUS_METERS as f64, RADAR_RADIUS_METERS as f64])
+						.paint(|ctx| {
+							ctx.draw(&Points {
+								coords: &nearby,
+								color: Color::Yellow,
+							});
+							ctx.draw(&Points {
+								coords: &alongside,
+								color: Color::Red,
+							});
+							ctx.draw(&Points {
+								coords: &[(0.0, 0.0)],
+								color: Color::Magenta,
+							});
+						});
+
+					  f.render_widget(radar, right_layout[1]);
+
+					  let (min_x, max_x, min_z, max_z) = data.track_path.iter().fold(
+						  (f32::MAX, f32::MIN, f32::MAX, f32::MIN),
+						  |(min_x, max_x, min_z, max_z), (x, z)| {
+							  (min_x.min(*x), max_x.max(*x), min_z.min(*z), max_z.max(*z))
+						  },
+					  );
+
+					  if min_x < max_x && min_z < max_z {
+						  let track_line: Vec<(f64, f64)> = data
+							.track_path
+							.iter()
+							.map(|(x, z)| (*x as f64, *z as f64))
+							.collect();
+
+						  let player_dot: Vec<(f64, f64)> = data
+							.minimap_cars
+							.iter()
+							.filter(|(_, _, is_player)| *is_player)
+							.map(|(x, z, _)| (*x as f64, *z as f64))
+							.collect();
+
+						  let rival_dots: Vec<(f64, f64)> = data
+							.minimap_cars
+							.iter()
+							.filter(|(_, _, is_player)| !is_player)
+							.map(|(x, z, _)| (*x as f64, *z as f64))
+							.collect();
+
+						  let minimap = Canvas::default()
+							.block(Block::default().title("Minimap").borders(Borders::ALL))
+							.x_bounds([min_x as f64, max_x as f64])
+							.y_bounds([min_z as f64, max_z as f64])
+							.paint(|ctx| {
+								ctx.draw(&Points {
+									coords: &track_line,
+									color: Color::DarkGray,
+								});
+								ctx.draw(&Points {
+									coords: &rival_dots,
+									color: Color::White,
+								});
+								ctx.draw(&Points {
+									coords: &player_dot,
+									color: Color::Magenta,
+								});
+							});
+
+						  f.render_widget(minimap, right_layout[2]);
+					  }
 				  })
 				  .expect("Error when draw terminal");
 			}
@@ -492,19 +830,61 @@ fn to_lap_time(lap_time: Duration) -> String {
 	format!("{}:{:.3}", mins, secs)
 }
 
-fn wear_color_percentage(value: usize) -> Color {
-	match value {
-		0..=50 => Color::Green,
-		51..=70 => Color::Yellow,
-		_ => Color::Red,
+fn delta_text(delta: Option<f32>) -> String {
+	match delta {
+		Some(delta) if delta >= 0.0 => format!("Delta: +{:.3}", delta),
+		Some(delta) => format!("Delta: {:.3}", delta),
+		None => "Delta: --".to_string(),
+	}
+}
+
+fn delta_style(delta: Option<f32>) -> Style {
+	match delta {
+		Some(delta) if delta < 0.0 => Style::default().fg(Color::Green),
+		Some(_) => Style::default().fg(Color::Red),
+		None => Style::default().fg(Color::White),
+	}
+}
+
+type ColorStop = (f32, (u8, u8, u8));
+
+const WEAR_COLOR_STOPS: [ColorStop; 3] = [
+	(0.0, (0, 200, 0)),
+	(0.5, (220, 200, 0)),
+	(1.0, (200, 0, 0)),
+];
+
+const PERFORMANCE_COLOR_STOPS: [ColorStop; 3] = [
+	(0.0, (200, 0, 0)),
+	(0.5, (220, 200, 0)),
+	(1.0, (0, 200, 0)),
+];
+
+fn gradient_color(value: f32, stops: &[ColorStop]) -> Color {
+	let value = value.clamp(stops[0].0, stops[stops.len() - 1].0);
+
+	for window in stops.windows(2) {
+		let (left_value, left_color) = window[0];
+		let (right_value, right_color) = window[1];
+
+		if value >= left_value && value <= right_value {
+			let a = (value - left_value) / (right_value - left_value);
+			let r = (left_color.0 as f32 * (1.0 - a) + right_color.0 as f32 * a).round() as u8;
+			let g = (left_color.1 as f32 * (1.0 - a) + right_color.1 as f32 * a).round() as u8;
+			let b = (left_color.2 as f32 * (1.0 - a) + right_color.2 as f32 * a).round() as u8;
+			return Color::Rgb(r, g, b);
+		}
 	}
+
+	let (_, last_color) = stops[stops.len() - 1];
+	Color::Rgb(last_color.0, last_color.1, last_color.2)
+}
+
+fn wear_color_percentage(value: usize) -> Color {
+	gradient_color(value as f32 / 100.0, &WEAR_COLOR_STOPS)
 }
 
 #[allow(dead_code)]
 fn color_percentage(value: usize) -> Color {
-	match value {
-		0..=30 => Color::Red,
-		31..=70 => Color::Yellow,
-		_ => Color::Green,
-	}
+	gradient_color(value as f32 / 100.0, &PERFORMANCE_COLOR_STOPS)
 }
@@ -1,37 +1,63 @@
-use async_std::io::Error;
+use async_std::io::{Cursor, Error};
+use async_std::net::{SocketAddr, UdpSocket};
 use async_std::task;
 use crossbeam_channel::unbounded;
-use f1_telemetry_client::{packet::Packet, Telemetry};
+use f1_telemetry_client::f1_2020::packet::parse_f12020;
+use std::net::IpAddr;
+use std::str::FromStr;
 
 mod app;
+mod recorder;
 
 #[async_std::main]
 async fn main() -> Result<(), Error> {
-    let ip_address = std::env::args().nth(1).expect("No IP Address given");
-    let port = std::env::args().nth(2).expect("No Port given");
-    let port = port.parse().expect("Port must be number");
-
-    let client = Telemetry::new(ip_address.as_str(), port).await.unwrap();
+    let mode = std::env::args().nth(1).expect("No mode given (live|record|replay)");
 
     let (tx, rx) = unbounded();
     let rx_clone = rx.clone();
     let mut app = app::App::new();
     app.start(rx_clone)?;
 
+    if mode == "replay" {
+        let path = std::env::args().nth(2).expect("No replay file given");
+        return recorder::replay(&path, tx).await;
+    }
+
+    let ip_address = std::env::args().nth(2).expect("No IP Address given");
+    let port = std::env::args().nth(3).expect("No Port given");
+    let port = port.parse().expect("Port must be number");
+
+    let ip = IpAddr::from_str(ip_address.as_str()).expect("Invalid ip address");
+    let socket = UdpSocket::bind(SocketAddr::new(ip, port)).await?;
+
+    let mut writer = if mode == "record" {
+        let path = std::env::args().nth(4).expect("No record file given");
+        Some(recorder::Writer::new(&path).expect("Unable to open record file"))
+    } else {
+        None
+    };
+
     loop {
-        match client.next().await {
-            Ok(p) => match p {
-                Packet::F12020(result) => {
-                    let sender = tx.clone();
-                    task::spawn(async move {
-                        match sender.send(result) {
-                            Ok(_) => {}
-                            Err(e) => eprintln!("Error send channel {}", e),
-                        }
-                    });
-                }
-                _ => unimplemented!(),
-            },
+        let mut buf = vec![0u8; recorder::MAX_FRAME_SIZE];
+        let (size, _) = socket.recv_from(&mut buf).await?;
+
+        if let Some(writer) = writer.as_mut() {
+            if let Err(e) = writer.write(&buf[..size]) {
+                eprintln!("Error writing record frame {}", e);
+            }
+        }
+
+        let mut cursor = Cursor::new(buf);
+        match parse_f12020(&mut cursor, size).await {
+            Ok(result) => {
+                let sender = tx.clone();
+                task::spawn(async move {
+                    match sender.send(result) {
+                        Ok(_) => {}
+                        Err(e) => eprintln!("Error send channel {}", e),
+                    }
+                });
+            }
             Err(e) => eprintln!("Error when receive UDP packet {}", e),
         }
     }
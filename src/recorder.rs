@@ -0,0 +1,80 @@
+use async_std::io::Cursor;
+use async_std::task;
+use crossbeam_channel::Sender;
+use f1_telemetry_client::f1_2020::packet::{parse_f12020, Packet2020};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+// Matches the recv buffer size f1_telemetry_client itself uses, so a frame
+// that doesn't fit could never have come from a real UDP datagram.
+pub const MAX_FRAME_SIZE: usize = 2048;
+
+pub struct Writer {
+	file: File,
+	started_at: Instant,
+}
+
+impl Writer {
+	pub fn new(path: &str) -> io::Result<Self> {
+		let file = OpenOptions::new()
+		  .write(true)
+		  .truncate(true)
+		  .create(true)
+		  .open(path)?;
+		Ok(Writer {
+			file,
+			started_at: Instant::now(),
+		})
+	}
+
+	pub fn write(&mut self, payload: &[u8]) -> io::Result<()> {
+		let elapsed_millis = self.started_at.elapsed().as_millis() as u64;
+
+		self.file.write_all(&elapsed_millis.to_le_bytes())?;
+		self.file.write_all(&(payload.len() as u32).to_le_bytes())?;
+		self.file.write_all(payload)?;
+		Ok(())
+	}
+}
+
+pub async fn replay(path: &str, sender: Sender<Packet2020>) -> io::Result<()> {
+	let mut file = File::open(path)?;
+	let mut last_elapsed = 0u64;
+
+	loop {
+		let mut timestamp_buf = [0u8; 8];
+		if file.read_exact(&mut timestamp_buf).is_err() {
+			break;
+		}
+		let elapsed_millis = u64::from_le_bytes(timestamp_buf);
+
+		let mut len_buf = [0u8; 4];
+		file.read_exact(&mut len_buf)?;
+		let len = u32::from_le_bytes(len_buf) as usize;
+		if len > MAX_FRAME_SIZE {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				"Replay frame exceeds max UDP frame size",
+			));
+		}
+
+		let mut payload = vec![0u8; len];
+		file.read_exact(&mut payload)?;
+
+		let delay = elapsed_millis.saturating_sub(last_elapsed);
+		last_elapsed = elapsed_millis;
+		task::sleep(Duration::from_millis(delay)).await;
+
+		let mut cursor = Cursor::new(payload);
+		let packet = parse_f12020(&mut cursor, len)
+		  .await
+		  .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+		if let Err(e) = sender.send(packet) {
+			eprintln!("Error send channel {}", e);
+			break;
+		}
+	}
+
+	Ok(())
+}